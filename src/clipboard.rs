@@ -0,0 +1,65 @@
+//! System clipboard abstraction.
+//!
+//! A `Clipboard` is provided by the running application and handed to widgets
+//! through the widget context, so that controls like `TextBox` can implement
+//! cut/copy/paste without depending on a concrete windowing backend.
+
+use std::rc::Rc;
+
+use widget::WidgetContainer;
+
+/// Read and write access to the system clipboard.
+pub trait Clipboard {
+    /// Returns the current clipboard contents as a `String`, or `None` if the
+    /// clipboard is empty or holds a non-text value.
+    fn get(&self) -> Option<String>;
+
+    /// Replaces the clipboard contents with `text`.
+    fn set(&self, text: String);
+}
+
+/// Fallback `Clipboard` used until a platform-backed implementation is wired
+/// in. It keeps the copied text in-process so cut/copy/paste stays functional
+/// within a single running application.
+#[derive(Default)]
+pub struct LocalClipboard {
+    content: ::std::cell::RefCell<Option<String>>,
+}
+
+impl Clipboard for LocalClipboard {
+    fn get(&self) -> Option<String> {
+        self.content.borrow().clone()
+    }
+
+    fn set(&self, text: String) {
+        *self.content.borrow_mut() = Some(text);
+    }
+}
+
+/// Property carrying the clipboard a widget uses for cut/copy/paste. It is set
+/// on the widget template during `create()` so event handlers reach the
+/// clipboard through their `WidgetContainer` rather than a global. Defaults to
+/// a process-local [`LocalClipboard`].
+#[derive(Clone)]
+pub struct ClipboardHandle(pub Rc<dyn Clipboard>);
+
+impl Default for ClipboardHandle {
+    fn default() -> Self {
+        ClipboardHandle(Rc::new(LocalClipboard::default()))
+    }
+}
+
+/// Access to the system clipboard from a widget context. Implemented for
+/// `WidgetContainer` so event handlers can reach the clipboard through the
+/// `widget` they are handed.
+pub trait ClipboardContext {
+    fn clipboard(&self) -> Rc<dyn Clipboard>;
+}
+
+impl ClipboardContext for WidgetContainer {
+    fn clipboard(&self) -> Rc<dyn Clipboard> {
+        self.borrow_property::<ClipboardHandle>()
+            .map(|handle| handle.0.clone())
+            .unwrap_or_else(|_| Rc::new(LocalClipboard::default()))
+    }
+}
@@ -0,0 +1,48 @@
+use dces::{Entity, EntityComponentManager};
+
+use layout_object::{measure_width, LayoutObject, LayoutResult};
+use structs::{Bounds, Constraint, Label};
+use theme::{Selector, Theme};
+use widget::{clamp_to_boundary, CaretOffset};
+
+/// The `CursorLayoutObject` positions a text cursor at the caret. It reads the
+/// caret byte offset published as `CaretOffset`, measures the width of the text
+/// preceding the caret against the theme font, and offsets the cursor's bounds
+/// on the x-axis so it lines up with the rendered glyphs instead of sitting at
+/// the start of the line.
+pub struct CursorLayoutObject;
+
+impl LayoutObject for CursorLayoutObject {
+    fn layout(
+        &self,
+        entity: Entity,
+        ecm: &mut EntityComponentManager,
+        constraint: &Constraint,
+        _children: &[Entity],
+        _size: Option<(u32, u32)>,
+        theme: &Theme,
+    ) -> LayoutResult {
+        let caret = ecm
+            .borrow_component::<CaretOffset>(entity)
+            .map(|c| c.0)
+            .unwrap_or(0);
+        let text = ecm
+            .borrow_component::<Label>(entity)
+            .map(|l| l.0.clone())
+            .unwrap_or_default();
+        let selector = ecm
+            .borrow_component::<Selector>(entity)
+            .map(|s| s.clone())
+            .unwrap_or_default();
+
+        let font_size = theme.uint("font-size", &selector).max(1);
+        let caret = clamp_to_boundary(&text, caret);
+        let x = measure_width(&text[..caret], font_size);
+
+        if let Ok(bounds) = ecm.borrow_mut_component::<Bounds>(entity) {
+            bounds.x = x;
+        }
+
+        LayoutResult::Size(constraint.width, constraint.height)
+    }
+}
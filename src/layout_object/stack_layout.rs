@@ -0,0 +1,131 @@
+use std::cell::Cell;
+
+use dces::{Entity, EntityComponentManager};
+
+use layout_object::{LayoutObject, LayoutResult};
+use structs::{Bounds, Constraint};
+use theme::Theme;
+use widget::{Orientation, Spacing};
+
+/// The `StackLayoutObject` lays its children out sequentially along a single
+/// axis, inserting `Spacing` pixels between consecutive children and stretching
+/// them on the cross axis.
+///
+/// For `Orientation::ZStack` it keeps the legacy behaviour of the `Stack`
+/// widget: every child is stretched to fill the parent and they overlap on the
+/// z-axis.
+#[derive(Default)]
+pub struct StackLayoutObject {
+    current_child: Cell<usize>,
+    // Running offset along the main axis while children are requested.
+    offset: Cell<u32>,
+}
+
+impl StackLayoutObject {
+    pub fn new() -> Self {
+        StackLayoutObject::default()
+    }
+}
+
+impl LayoutObject for StackLayoutObject {
+    fn layout(
+        &self,
+        entity: Entity,
+        ecm: &mut EntityComponentManager,
+        constraint: &Constraint,
+        children: &[Entity],
+        size: Option<(u32, u32)>,
+        _theme: &Theme,
+    ) -> LayoutResult {
+        let orientation = ecm
+            .borrow_component::<Orientation>(entity)
+            .map(|o| *o)
+            .unwrap_or_default();
+        let spacing = ecm
+            .borrow_component::<Spacing>(entity)
+            .map(|s| s.0)
+            .unwrap_or(0);
+
+        if children.is_empty() {
+            return LayoutResult::Size(constraint.width, constraint.height);
+        }
+
+        // Position the child that was just measured, then request the next one.
+        if let Some(size) = size {
+            let index = self.current_child.get();
+
+            if let Ok(bounds) = ecm.borrow_mut_component::<Bounds>(children[index]) {
+                match orientation {
+                    Orientation::Horizontal => {
+                        bounds.x = self.offset.get();
+                        bounds.y = 0;
+                        bounds.height = constraint.height;
+                        self.offset.set(self.offset.get() + size.0 + spacing);
+                    }
+                    Orientation::Vertical => {
+                        bounds.x = 0;
+                        bounds.y = self.offset.get();
+                        bounds.width = constraint.width;
+                        self.offset.set(self.offset.get() + size.1 + spacing);
+                    }
+                    Orientation::ZStack => {
+                        bounds.x = 0;
+                        bounds.y = 0;
+                        bounds.width = constraint.width;
+                        bounds.height = constraint.height;
+                    }
+                }
+            }
+
+            self.current_child.set(index + 1);
+
+            if self.current_child.get() < children.len() {
+                return LayoutResult::RequestChild(children[self.current_child.get()], *constraint);
+            }
+
+            // All children placed: the stack spans the accumulated main axis
+            // (minus the trailing spacing) for the linear orientations.
+            let extent = main_extent(self.offset.get(), spacing);
+            return match orientation {
+                Orientation::Horizontal => LayoutResult::Size(extent, constraint.height),
+                Orientation::Vertical => LayoutResult::Size(constraint.width, extent),
+                Orientation::ZStack => LayoutResult::Size(constraint.width, constraint.height),
+            };
+        }
+
+        // First pass: reset the accumulators and request the first child.
+        self.current_child.set(0);
+        self.offset.set(0);
+        LayoutResult::RequestChild(children[0], *constraint)
+    }
+}
+
+/// Main-axis extent of a linear stack, given the running `offset` accumulated
+/// as `size + spacing` per child. The trailing spacing after the last child is
+/// removed; `saturating_sub` keeps an empty or spacing-only stack at zero.
+fn main_extent(offset: u32, spacing: u32) -> u32 {
+    offset.saturating_sub(spacing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::main_extent;
+
+    #[test]
+    fn trailing_spacing_is_removed() {
+        // Two 10px children with 4px spacing: offset = 10+4 + 10+4 = 28.
+        assert_eq!(main_extent(28, 4), 24);
+    }
+
+    #[test]
+    fn single_child_has_no_spacing() {
+        // One 10px child: offset = 10 + 4.
+        assert_eq!(main_extent(14, 4), 10);
+    }
+
+    #[test]
+    fn spacing_only_does_not_underflow() {
+        assert_eq!(main_extent(0, 4), 0);
+        assert_eq!(main_extent(4, 4), 0);
+    }
+}
@@ -0,0 +1,302 @@
+use std::cell::RefCell;
+
+use dces::{Entity, EntityComponentManager};
+
+use layout_object::{LayoutObject, LayoutResult};
+use structs::{Constraint, Label};
+use theme::{resolve_selector, Selector, Theme};
+use widget::{Justification, LineBreak};
+
+/// Horizontal advance in pixels of a single glyph at `font_size`.
+///
+/// OrbTk ships without a glyph-metrics backend in this layer, so we model a
+/// simple proportional font: spaces are narrower than other glyphs. The advance
+/// is derived from the theme's `font-size` so wrapping scales with the theme.
+pub fn glyph_advance(c: char, font_size: u32) -> u32 {
+    if c == ' ' {
+        font_size * 3 / 10
+    } else {
+        font_size * 6 / 10
+    }
+}
+
+/// Total advance of `text` at `font_size`.
+pub fn measure_width(text: &str, font_size: u32) -> u32 {
+    text.chars().map(|c| glyph_advance(c, font_size)).sum()
+}
+
+/// A single laid-out visual line: the byte range into the source string, its
+/// x-offset after justification and its pixel width.
+pub struct LineBox {
+    pub start: usize,
+    pub end: usize,
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+}
+
+/// An unjustified visual line produced by [`wrap_text`]: the byte range into the
+/// source string and the pixel width of the laid-out glyphs.
+pub struct WrappedLine {
+    pub start: usize,
+    pub end: usize,
+    pub width: u32,
+}
+
+/// Greedily wraps `text` into visual lines no wider than `available_width`.
+///
+/// `advance` returns the horizontal advance in pixels for a single `char`; it
+/// is supplied by the caller so the algorithm stays independent of the font
+/// backend. Explicit newlines always start a new line. When a single word is
+/// wider than `available_width` it is broken at the last character that fits
+/// under `LineBreak::AnyCharacter`, and kept whole (overflowing) otherwise.
+pub fn wrap_text<F>(
+    text: &str,
+    available_width: u32,
+    line_break: LineBreak,
+    advance: F,
+) -> Vec<WrappedLine>
+where
+    F: Fn(char) -> u32,
+{
+    let mut lines = Vec::new();
+    let mut line_start = 0;
+    let mut line_width = 0;
+    // Byte offset and width-so-far at the last whitespace on the current line,
+    // used as the break point for word-boundary wrapping.
+    let mut last_space: Option<(usize, u32)> = None;
+
+    for (i, c) in text.char_indices() {
+        if c == '\n' {
+            lines.push(WrappedLine {
+                start: line_start,
+                end: i,
+                width: line_width,
+            });
+            line_start = i + 1;
+            line_width = 0;
+            last_space = None;
+            continue;
+        }
+
+        let w = advance(c);
+
+        if line_width + w > available_width && i > line_start {
+            match (last_space, line_break) {
+                (_, LineBreak::WordBoundary) if c.is_whitespace() => {
+                    // The overflowing char is itself whitespace: the line ends
+                    // right before it and the whitespace is dropped.
+                    lines.push(WrappedLine {
+                        start: line_start,
+                        end: i,
+                        width: line_width,
+                    });
+                    line_start = i + c.len_utf8();
+                    line_width = 0;
+                    last_space = None;
+                }
+                (Some((space, width_before)), LineBreak::WordBoundary) => {
+                    lines.push(WrappedLine {
+                        start: line_start,
+                        end: space,
+                        width: width_before,
+                    });
+                    // Recompute the trailing segment width directly from the
+                    // text so mixed whitespace (e.g. tabs) cannot underflow.
+                    let next_start = space + 1;
+                    line_start = next_start;
+                    line_width = text[next_start..i].chars().map(&advance).sum::<u32>() + w;
+                    last_space = None;
+                }
+                (None, LineBreak::WordBoundary) => {
+                    // A single word wider than the line and nowhere to break:
+                    // let it overflow rather than splitting mid-word.
+                    line_width += w;
+                }
+                _ => {
+                    // AnyCharacter: break at the last character that fits.
+                    lines.push(WrappedLine {
+                        start: line_start,
+                        end: i,
+                        width: line_width,
+                    });
+                    line_start = i;
+                    line_width = w;
+                    last_space = None;
+                }
+            }
+        } else {
+            if c.is_whitespace() {
+                last_space = Some((i, line_width));
+            }
+            line_width += w;
+        }
+    }
+
+    lines.push(WrappedLine {
+        start: line_start,
+        end: text.len(),
+        width: line_width,
+    });
+
+    lines
+}
+
+/// Horizontal offset of a line of `line_width` pixels inside `available_width`
+/// for the given justification.
+pub fn justify_offset(justification: Justification, available_width: u32, line_width: u32) -> u32 {
+    let free = available_width.saturating_sub(line_width);
+    match justification {
+        Justification::Left => 0,
+        Justification::Center => free / 2,
+        Justification::Right => free,
+    }
+}
+
+/// The `TextWrapLayoutObject` lays out a block of text on multiple lines. It
+/// greedily packs words onto a line until the next word would exceed the
+/// available width, honouring explicit `\n`, and offsets each line on the
+/// x-axis according to the requested `Justification`.
+///
+/// The computed line boxes are cached on the object so the matching render
+/// object can draw each line at its justified position.
+#[derive(Default)]
+pub struct TextWrapLayoutObject {
+    lines: RefCell<Vec<LineBox>>,
+}
+
+impl TextWrapLayoutObject {
+    pub fn new() -> Self {
+        TextWrapLayoutObject::default()
+    }
+
+    /// The most recently laid-out line boxes, consumed by the render object.
+    pub fn lines(&self) -> &RefCell<Vec<LineBox>> {
+        &self.lines
+    }
+}
+
+impl LayoutObject for TextWrapLayoutObject {
+    fn layout(
+        &self,
+        entity: Entity,
+        ecm: &mut EntityComponentManager,
+        constraint: &Constraint,
+        _children: &[Entity],
+        _size: Option<(u32, u32)>,
+        theme: &Theme,
+    ) -> LayoutResult {
+        let text = ecm
+            .borrow_component::<Label>(entity)
+            .map(|l| l.0.clone())
+            .unwrap_or_default();
+        let justification = ecm
+            .borrow_component::<Justification>(entity)
+            .map(|j| *j)
+            .unwrap_or_default();
+        let line_break = ecm
+            .borrow_component::<LineBreak>(entity)
+            .map(|b| *b)
+            .unwrap_or_default();
+        let selector = ecm
+            .borrow_component::<Selector>(entity)
+            .map(|s| s.clone())
+            .unwrap_or_default();
+
+        // Pick up a runtime theme swap: a swap marks the selector dirty, so
+        // re-resolve it here before pulling style values for this pass.
+        resolve_selector(&selector);
+
+        let font_size = theme.uint("font-size", &selector).max(1);
+        let line_height = font_size + font_size / 5;
+        let width = constraint.width;
+
+        let wrapped = wrap_text(&text, width, line_break, |c| glyph_advance(c, font_size));
+
+        let mut boxes = Vec::with_capacity(wrapped.len());
+        for (row, line) in wrapped.iter().enumerate() {
+            boxes.push(LineBox {
+                start: line.start,
+                end: line.end,
+                x: justify_offset(justification, width, line.width),
+                y: row as u32 * line_height,
+                width: line.width,
+            });
+        }
+
+        let height = (wrapped.len() as u32 * line_height).max(constraint.height);
+        *self.lines.borrow_mut() = boxes;
+
+        LayoutResult::Size(width, height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use widget::{Justification, LineBreak};
+
+    // Fixed-width advance so the wrap maths are easy to reason about.
+    fn unit(_c: char) -> u32 {
+        1
+    }
+
+    #[test]
+    fn wraps_on_word_boundary() {
+        let lines = wrap_text("foo bar baz", 7, LineBreak::WordBoundary, unit);
+        let text = "foo bar baz";
+        let rendered: Vec<&str> = lines.iter().map(|l| &text[l.start..l.end]).collect();
+        assert_eq!(rendered, vec!["foo bar", "baz"]);
+    }
+
+    #[test]
+    fn honors_explicit_newline() {
+        let lines = wrap_text("a\nb", 80, LineBreak::WordBoundary, unit);
+        let text = "a\nb";
+        let rendered: Vec<&str> = lines.iter().map(|l| &text[l.start..l.end]).collect();
+        assert_eq!(rendered, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn long_word_stays_whole_on_word_boundary() {
+        // A single word wider than the line overflows rather than splitting.
+        let lines = wrap_text("abcdefgh", 3, LineBreak::WordBoundary, unit);
+        assert_eq!(lines.len(), 1);
+    }
+
+    #[test]
+    fn breaks_mid_word_under_any_character() {
+        let lines = wrap_text("abcdefgh", 3, LineBreak::AnyCharacter, unit);
+        assert!(lines.len() > 1);
+    }
+
+    #[test]
+    fn tab_whitespace_does_not_underflow() {
+        // A tab is whitespace with a non-space advance; the break must not
+        // underflow when the trailing segment width is computed.
+        let lines = wrap_text("aa\tbbbb", 4, LineBreak::WordBoundary, |c| {
+            if c == '\t' {
+                4
+            } else {
+                1
+            }
+        });
+        assert!(lines.len() >= 2);
+    }
+
+    #[test]
+    fn justify_offset_centers_and_right_aligns() {
+        assert_eq!(justify_offset(Justification::Left, 10, 4), 0);
+        assert_eq!(justify_offset(Justification::Center, 10, 4), 3);
+        assert_eq!(justify_offset(Justification::Right, 10, 4), 6);
+        // Overflowing line never produces a negative / wrapped offset.
+        assert_eq!(justify_offset(Justification::Right, 4, 10), 0);
+    }
+
+    #[test]
+    fn measure_width_sums_glyphs() {
+        let fs = 10;
+        assert_eq!(measure_width("ab", fs), glyph_advance('a', fs) + glyph_advance('b', fs));
+        assert!(glyph_advance(' ', fs) < glyph_advance('a', fs));
+    }
+}
@@ -0,0 +1,142 @@
+use std::cell::Cell;
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use dces::{Entity, EntityComponentManager};
+
+use theme::Theme;
+
+/// The theme currently installed on the tree, stored as a component so the
+/// layout/render pass can read it back through the `EntityComponentManager`
+/// instead of a global. Swapping it is done through [`ThemeContext::set_theme`],
+/// which also marks every live [`Selector`] dirty.
+#[derive(Clone)]
+pub struct ActiveTheme(pub Rc<Theme>);
+
+/// A `Selector` is the CSS-style handle a widget uses to request its style from
+/// the active `Theme` (e.g. `Selector::new().with("textbox")`).
+///
+/// The resolved style is cached by the render/layout pass; `dirty` is the signal
+/// telling that pass to re-resolve the selector. Swapping the theme at runtime
+/// marks every live selector dirty (see [`ThemeContext::set_theme`]) so the next
+/// pass pulls fresh style values.
+#[derive(Clone, Debug, Default)]
+pub struct Selector {
+    pub element: Option<String>,
+    pub classes: HashSet<String>,
+    pub id: Option<String>,
+    dirty: Cell<bool>,
+}
+
+impl Selector {
+    pub fn new() -> Self {
+        Selector {
+            // A fresh selector has nothing resolved yet, so it starts dirty.
+            dirty: Cell::new(true),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the element name of the selector.
+    pub fn with(mut self, element: &str) -> Self {
+        self.element = Some(element.to_string());
+        self.dirty.set(true);
+        self
+    }
+
+    /// Adds a style class to the selector.
+    pub fn class(mut self, class: &str) -> Self {
+        self.classes.insert(class.to_string());
+        self.dirty.set(true);
+        self
+    }
+
+    /// Returns `true` when the cached style is stale and must be re-resolved.
+    pub fn dirty(&self) -> bool {
+        self.dirty.get()
+    }
+
+    /// Explicitly marks the selector as needing re-resolution.
+    pub fn set_dirty(&self, dirty: bool) {
+        self.dirty.set(dirty);
+    }
+
+    /// Records that the selector has just been resolved against the current
+    /// theme, clearing the dirty state until the next theme swap.
+    pub fn mark_resolved(&self) {
+        self.dirty.set(false);
+    }
+}
+
+/// Re-resolves `selector` against the active theme if it is dirty, returning
+/// `true` when a re-resolution happened (i.e. the owning widget should repaint).
+///
+/// This is the layout/render-pass consumer of the dirty flag: a layout object
+/// that calls it while laying out (as `TextWrapLayoutObject` does) picks up a
+/// runtime theme swap and is repainted.
+pub fn resolve_selector(selector: &Selector) -> bool {
+    if !selector.dirty() {
+        return false;
+    }
+    // The actual style lookup is performed by the render object against the
+    // `ActiveTheme` component; here we simply clear the flag once it has been
+    // pulled.
+    selector.mark_resolved();
+    true
+}
+
+/// Access to the active theme from the widget tree. Implemented for
+/// `EntityComponentManager` so a light/dark toggle can install a new theme and
+/// have every widget re-resolve its selector on the next pass.
+pub trait ThemeContext {
+    /// Installs `theme` as the active theme and marks every selector dirty so
+    /// the next layout pass re-resolves against it.
+    fn set_theme(&mut self, theme: Rc<Theme>);
+
+    /// The currently installed theme, if one has been set.
+    fn theme(&self) -> Option<Rc<Theme>>;
+}
+
+impl ThemeContext for EntityComponentManager {
+    fn set_theme(&mut self, theme: Rc<Theme>) {
+        let entities: Vec<Entity> = self.entities().iter().cloned().collect();
+        for entity in entities {
+            if let Ok(selector) = self.borrow_component::<Selector>(entity) {
+                selector.set_dirty(true);
+            }
+            self.register_component(entity, ActiveTheme(theme.clone()));
+        }
+    }
+
+    fn theme(&self) -> Option<Rc<Theme>> {
+        self.entities()
+            .iter()
+            .find_map(|entity| self.borrow_component::<ActiveTheme>(*entity).ok())
+            .map(|active| active.0.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_selector_starts_dirty() {
+        assert!(Selector::new().dirty());
+    }
+
+    #[test]
+    fn resolving_clears_dirty() {
+        let selector = Selector::new().with("textbox");
+        assert!(resolve_selector(&selector));
+        // Already resolved: no work, no repaint.
+        assert!(!selector.dirty());
+        assert!(!resolve_selector(&selector));
+
+        // A theme swap marks the selector dirty again.
+        selector.set_dirty(true);
+        assert!(selector.dirty());
+        assert!(resolve_selector(&selector));
+        assert!(!selector.dirty());
+    }
+}
@@ -0,0 +1,104 @@
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+/// A `SharedProperty` wraps a single widget property behind an `Rc` so the same
+/// value can be attached to several widgets at once (for example the `Label`
+/// shared between a `TextBox` and its inner `WaterMarkTextBlock`).
+///
+/// In addition to sharing the value, a `SharedProperty` can notify interested
+/// parties when the value is mutated. A widget state registers a callback with
+/// [`SharedProperty::on_changed`] and is then driven by the change instead of
+/// re-reading and diffing the value every frame.
+#[derive(Clone)]
+pub struct SharedProperty {
+    pub property: Rc<RefCell<Box<dyn Any>>>,
+    pub type_id: TypeId,
+    observers: Rc<RefCell<Vec<Weak<dyn Fn()>>>>,
+}
+
+impl SharedProperty {
+    pub fn new<P: Any>(property: P) -> Self {
+        SharedProperty {
+            property: Rc::new(RefCell::new(Box::new(property))),
+            type_id: TypeId::of::<P>(),
+            observers: Rc::new(RefCell::new(Vec::new())),
+        }
+    }
+
+    /// Replaces the stored value and notifies every live observer.
+    pub fn set<P: Any>(&self, value: P) {
+        *self.property.borrow_mut() = Box::new(value);
+        self.notify();
+    }
+
+    /// Registers `callback` to be invoked with the current value whenever the
+    /// value is replaced through [`SharedProperty::set`] or [`SharedProperty::notify`]
+    /// is called; writes made directly through `property` do not invoke it. The returned `Rc` owns the subscription: the
+    /// callback stays registered for as long as the caller holds on to it, and
+    /// is dropped automatically once it does not.
+    pub fn on_changed<P: Any, F: Fn(&P) + 'static>(&self, callback: F) -> Rc<dyn Fn()> {
+        let property = self.property.clone();
+        let observer: Rc<dyn Fn()> = Rc::new(move || {
+            if let Some(value) = property.borrow().downcast_ref::<P>() {
+                callback(value);
+            }
+        });
+
+        self.observers.borrow_mut().push(Rc::downgrade(&observer));
+        observer
+    }
+
+    /// Invokes every live observer, dropping any whose owner has gone away.
+    pub fn notify(&self) {
+        self.observers
+            .borrow_mut()
+            .retain(|observer| observer.upgrade().is_some());
+
+        let observers: Vec<Rc<dyn Fn()>> = self
+            .observers
+            .borrow()
+            .iter()
+            .filter_map(Weak::upgrade)
+            .collect();
+
+        for observer in observers {
+            observer();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn set_notifies_live_observers_with_current_value() {
+        let property = SharedProperty::new(1u32);
+        let seen = Rc::new(Cell::new(0u32));
+
+        let seen_clone = seen.clone();
+        let _guard = property.on_changed::<u32, _>(move |value| seen_clone.set(*value));
+
+        property.set(42u32);
+        assert_eq!(seen.get(), 42);
+    }
+
+    #[test]
+    fn dropped_observer_is_not_called() {
+        let property = SharedProperty::new(0u32);
+        let hits = Rc::new(Cell::new(0u32));
+
+        let hits_clone = hits.clone();
+        let guard = property.on_changed::<u32, _>(move |_| hits_clone.set(hits_clone.get() + 1));
+
+        property.set(1u32);
+        assert_eq!(hits.get(), 1);
+
+        // Dropping the guard unregisters the callback; the weak ref is pruned.
+        drop(guard);
+        property.set(2u32);
+        assert_eq!(hits.get(), 1);
+    }
+}
@@ -1,20 +1,48 @@
-use widget::{Template, Widget};
-use layout_object::StretchLayoutObject;
 use enums::ParentType;
+use layout_object::StackLayoutObject;
+use widget::{Template, Widget};
+
+/// Space in pixels inserted between consecutive children of a linear `Stack`.
+#[derive(Clone, Copy, Default, PartialEq)]
+pub struct Spacing(pub u32);
+
+/// Orientation of a `Stack`: children are laid out along the given axis, or
+/// overlapped on the z-axis for `ZStack`.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Orientation {
+    Horizontal,
+    Vertical,
+    ZStack,
+}
+
+impl Default for Orientation {
+    fn default() -> Self {
+        Orientation::ZStack
+    }
+}
 
-/// The `Stack` represents a layout widget that is used to stack its children on the z-axis.
-/// 
+/// The `Stack` represents a layout widget that stacks its children along an
+/// axis (`Horizontal`/`Vertical`) or overlaps them on the z-axis (`ZStack`).
+///
+/// # Properties
+///
+/// * `Orientation` - Axis the children are stacked along. Defaults to `ZStack`.
+/// * `Spacing` - Pixels inserted between consecutive children for the linear
+///   orientations.
+///
 /// # Others
-/// 
+///
 /// * `ParentType`- Mutli.
-/// * `StretchLayoutObject` - Used to layout the widget.
+/// * `StackLayoutObject` - Used to layout the widget.
 pub struct Stack;
 
 impl Widget for Stack {
     fn create() -> Template {
         Template::default()
             .as_parent_type(ParentType::Multi)
-            .with_layout_object(StretchLayoutObject)
+            .with_property(Orientation::default())
+            .with_property(Spacing::default())
+            .with_layout_object(StackLayoutObject::new())
             .with_debug_name("Stack")
     }
-}
\ No newline at end of file
+}
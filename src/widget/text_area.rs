@@ -0,0 +1,433 @@
+use clipboard::{Clipboard, ClipboardContext, ClipboardHandle};
+use enums::ParentType;
+use event::{Key, KeyEventHandler};
+use layout_object::{CursorLayoutObject, TextWrapLayoutObject};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use structs::{Focused, Label, WaterMark};
+use theme::Selector;
+use widget::{
+    clamp_to_boundary, CaretOffset, Container, Cursor, ScrollViewer, SharedProperty, State,
+    Template, WaterMarkTextBlock, Widget, WidgetContainer,
+};
+
+/// Horizontal justification of the wrapped lines of a `TextArea`.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Justification {
+    Left,
+    Center,
+    Right,
+}
+
+impl Default for Justification {
+    fn default() -> Self {
+        Justification::Left
+    }
+}
+
+/// Policy used when a word does not fit on the current line.
+#[derive(Clone, Copy, PartialEq)]
+pub enum LineBreak {
+    /// Break at the last whitespace that fits; overflow words longer than the
+    /// line rather than splitting them.
+    WordBoundary,
+    /// Break mid-word at the last character that fits.
+    AnyCharacter,
+}
+
+impl Default for LineBreak {
+    fn default() -> Self {
+        LineBreak::WordBoundary
+    }
+}
+
+/// The `TextAreaState` handles the text processing of the `TextArea` widget.
+///
+/// It shares the caret/selection model of `TextBoxState` but additionally
+/// understands newlines: `Enter` inserts a `\n`, and `Up`/`Down` move the caret
+/// to the nearest column on the adjacent line.
+#[derive(Default)]
+pub struct TextAreaState {
+    text: RefCell<String>,
+    focused: Cell<bool>,
+    updated: Cell<bool>,
+    caret: Cell<usize>,
+    selection_anchor: Cell<Option<usize>>,
+}
+
+impl Into<Rc<State>> for TextAreaState {
+    fn into(self) -> Rc<State> {
+        Rc::new(self)
+    }
+}
+
+impl TextAreaState {
+    fn selection(&self) -> Option<(usize, usize)> {
+        self.selection_anchor.get().and_then(|anchor| {
+            let caret = self.caret.get();
+            if anchor == caret {
+                None
+            } else if anchor < caret {
+                Some((anchor, caret))
+            } else {
+                Some((caret, anchor))
+            }
+        })
+    }
+
+    fn delete_selection(&self) -> bool {
+        if let Some((start, end)) = self.selection() {
+            self.text.borrow_mut().drain(start..end);
+            self.caret.set(start);
+            self.selection_anchor.set(None);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn prev_boundary(&self) -> usize {
+        let text = self.text.borrow();
+        text[..self.caret.get()]
+            .char_indices()
+            .next_back()
+            .map(|(i, _)| i)
+            .unwrap_or(0)
+    }
+
+    fn next_boundary(&self) -> usize {
+        let text = self.text.borrow();
+        let caret = self.caret.get();
+        match text[caret..].char_indices().nth(1) {
+            Some((i, _)) => caret + i,
+            None => text.len(),
+        }
+    }
+
+    /// Byte offset of the start of the line containing `at`.
+    fn line_start(&self, at: usize) -> usize {
+        let text = self.text.borrow();
+        text[..at].rfind('\n').map(|i| i + 1).unwrap_or(0)
+    }
+
+    /// Byte offset of the end of the line containing `at` (the next `\n` or the
+    /// end of the buffer).
+    fn line_end(&self, at: usize) -> usize {
+        let text = self.text.borrow();
+        text[at..].find('\n').map(|i| at + i).unwrap_or(text.len())
+    }
+
+    /// Moves the caret to the nearest column on the line above or below. The
+    /// column is expressed in chars so multi-byte content keeps the caret on a
+    /// boundary.
+    fn move_vertical(&self, down: bool) {
+        let offset = vertical_target(&self.text.borrow(), self.caret.get(), down);
+        self.caret.set(offset);
+    }
+
+    fn prepare_move(&self, shift: bool) {
+        if shift {
+            if self.selection_anchor.get().is_none() {
+                self.selection_anchor.set(Some(self.caret.get()));
+            }
+        } else {
+            self.selection_anchor.set(None);
+        }
+    }
+
+    fn update_text(&self, key: Key) -> bool {
+        if !self.focused.get() {
+            return false;
+        }
+
+        let shift = key.shift();
+
+        match <Option<u8>>::from(key) {
+            Some(byte) => {
+                self.delete_selection();
+                let c = byte as char;
+                let caret = self.caret.get();
+                self.text.borrow_mut().insert(caret, c);
+                self.caret.set(caret + c.len_utf8());
+            }
+            None => match key {
+                Key::Enter => {
+                    self.delete_selection();
+                    let caret = self.caret.get();
+                    self.text.borrow_mut().insert(caret, '\n');
+                    self.caret.set(caret + 1);
+                }
+                Key::Left => {
+                    self.prepare_move(shift);
+                    self.caret.set(self.prev_boundary());
+                }
+                Key::Right => {
+                    self.prepare_move(shift);
+                    self.caret.set(self.next_boundary());
+                }
+                Key::Up => {
+                    self.prepare_move(shift);
+                    self.move_vertical(false);
+                }
+                Key::Down => {
+                    self.prepare_move(shift);
+                    self.move_vertical(true);
+                }
+                Key::Home => {
+                    self.prepare_move(shift);
+                    let start = self.line_start(self.caret.get());
+                    self.caret.set(start);
+                }
+                Key::End => {
+                    self.prepare_move(shift);
+                    let end = self.line_end(self.caret.get());
+                    self.caret.set(end);
+                }
+                Key::Backspace => {
+                    if !self.delete_selection() {
+                        let start = self.prev_boundary();
+                        let caret = self.caret.get();
+                        if start != caret {
+                            self.text.borrow_mut().drain(start..caret);
+                            self.caret.set(start);
+                        }
+                    }
+                }
+                Key::Delete => {
+                    if !self.delete_selection() {
+                        let end = self.next_boundary();
+                        let caret = self.caret.get();
+                        if end != caret {
+                            self.text.borrow_mut().drain(caret..end);
+                        }
+                    }
+                }
+                _ => {}
+            },
+        }
+
+        self.clamp_caret();
+        self.updated.set(true);
+
+        true
+    }
+
+    fn selected_or_all(&self) -> String {
+        let text = self.text.borrow();
+        match self.selection() {
+            Some((start, end)) => text[start..end].to_string(),
+            None => text.clone(),
+        }
+    }
+
+    fn handle_clipboard(&self, key: Key, clipboard: &dyn Clipboard) -> bool {
+        if !self.focused.get() {
+            return false;
+        }
+
+        match <Option<u8>>::from(key).map(|byte| (byte as char).to_ascii_lowercase()) {
+            Some('c') => {
+                clipboard.set(self.selected_or_all());
+            }
+            Some('x') => {
+                clipboard.set(self.selected_or_all());
+                self.delete_selection();
+            }
+            Some('v') => {
+                if let Some(pasted) = clipboard.get() {
+                    self.delete_selection();
+                    let caret = self.caret.get();
+                    self.text.borrow_mut().insert_str(caret, &pasted);
+                    self.caret.set(caret + pasted.len());
+                    self.selection_anchor.set(None);
+                }
+            }
+            _ => return false,
+        }
+
+        self.clamp_caret();
+        self.updated.set(true);
+
+        true
+    }
+
+    fn clamp_caret(&self) {
+        let text = self.text.borrow();
+        self.caret.set(clamp_to_boundary(&text, self.caret.get()));
+
+        if let Some(anchor) = self.selection_anchor.get() {
+            self.selection_anchor
+                .set(Some(clamp_to_boundary(&text, anchor)));
+        }
+    }
+}
+
+impl State for TextAreaState {
+    fn update(&self, widget: &mut WidgetContainer) {
+        if let Ok(focused) = widget.borrow_property::<Focused>() {
+            self.focused.set(focused.0);
+        }
+
+        if let Ok(label) = widget.borrow_mut_property::<Label>() {
+            if label.0 != *self.text.borrow() {
+                if self.updated.get() {
+                    label.0 = self.text.borrow().clone();
+                } else {
+                    *self.text.borrow_mut() = label.0.clone();
+                    self.caret.set(self.text.borrow().len());
+                    self.selection_anchor.set(None);
+                }
+            }
+
+            self.updated.set(false);
+        }
+
+        if let Ok(offset) = widget.borrow_mut_property::<CaretOffset>() {
+            offset.0 = self.caret.get();
+        }
+    }
+}
+
+/// The `TextArea` represents a multiline text input widget with soft
+/// word-wrapping and horizontal justification.
+///
+/// # Shared Properties
+///
+/// * `Label` - String used to display the text of the text area.
+/// * `Watermark` - String used to display a placeholder text if `Label` string is empty.
+/// * `Selector` - CSS selector used to request the theme of the widget.
+///
+/// # Properties
+///
+/// * `Focused` - Defines if the widget is focues and handles the current text input.
+/// * `Justification` - Horizontal justification of the wrapped lines.
+/// * `LineBreak` - Policy used when a word does not fit on the current line.
+///
+/// # Others
+///
+/// * `TextAreaState` - Handles the inner state of the widget.
+/// * `TextWrapLayoutObject` - Wraps and justifies the content.
+/// * `KeyEventHandler` - Process the text input of the control if it is focuesd.
+pub struct TextArea;
+
+impl Widget for TextArea {
+    fn create() -> Template {
+        let label = SharedProperty::new(Label::default());
+        let water_mark = SharedProperty::new(WaterMark::default());
+        let selector = SharedProperty::new(Selector::new().with("textarea"));
+        let caret_offset = SharedProperty::new(CaretOffset::default());
+        let state = Rc::new(TextAreaState::default());
+
+        Template::default()
+            .as_parent_type(ParentType::Single)
+            .with_property(Focused(false))
+            .with_property(ClipboardHandle::default())
+            .with_property(Justification::default())
+            .with_property(LineBreak::default())
+            .with_child(
+                Container::create()
+                    .with_child(
+                        ScrollViewer::create().with_child(
+                            WaterMarkTextBlock::create()
+                                .with_layout_object(TextWrapLayoutObject::new())
+                                .with_child(
+                                    Cursor::create()
+                                        .with_layout_object(CursorLayoutObject)
+                                        .with_shared_property(caret_offset.clone())
+                                        .with_shared_property(label.clone())
+                                        .with_shared_property(selector.clone()),
+                                )
+                                .with_shared_property(label.clone())
+                                .with_shared_property(selector.clone())
+                                .with_shared_property(water_mark.clone()),
+                        ),
+                    )
+                    .with_shared_property(selector.clone()),
+            )
+            .with_state(state.clone())
+            .with_debug_name("TextArea")
+            .with_shared_property(label)
+            .with_shared_property(selector)
+            .with_shared_property(water_mark)
+            .with_shared_property(caret_offset)
+            .with_event_handler(KeyEventHandler::default().on_key_down(Rc::new(
+                move |key: Key, widget: &mut WidgetContainer| -> bool {
+                    if key.control() {
+                        let clipboard = widget.clipboard();
+                        state.handle_clipboard(key, &*clipboard)
+                    } else {
+                        state.update_text(key)
+                    }
+                },
+            )))
+    }
+}
+
+/// Maps `caret` to the nearest column on the line above (`down == false`) or
+/// below (`down == true`). Columns are counted in `char`s so the result always
+/// lands on a boundary; if the adjacent line is shorter the caret clamps to its
+/// end. Returns `caret` unchanged when there is no adjacent line.
+fn vertical_target(text: &str, caret: usize, down: bool) -> usize {
+    let start = text[..caret].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let end = text[caret..].find('\n').map(|i| caret + i).unwrap_or(text.len());
+    let column = text[start..caret].chars().count();
+
+    let target_start = if down {
+        if end >= text.len() {
+            return caret;
+        }
+        end + 1
+    } else {
+        if start == 0 {
+            return caret;
+        }
+        text[..start - 1].rfind('\n').map(|i| i + 1).unwrap_or(0)
+    };
+
+    let target_end = text[target_start..]
+        .find('\n')
+        .map(|i| target_start + i)
+        .unwrap_or(text.len());
+
+    let mut offset = target_end;
+    for (n, (i, _)) in text[target_start..target_end].char_indices().enumerate() {
+        if n == column {
+            offset = target_start + i;
+            break;
+        }
+    }
+    offset
+}
+
+#[cfg(test)]
+mod tests {
+    use super::vertical_target;
+
+    #[test]
+    fn down_keeps_column() {
+        // caret after "ab" on line 0 -> same column on line 1.
+        let text = "abc\ndef";
+        assert_eq!(vertical_target(text, 2, true), 6);
+    }
+
+    #[test]
+    fn up_keeps_column() {
+        let text = "abc\ndef";
+        // caret after "de" on line 1 (offset 6) -> column 2 on line 0.
+        assert_eq!(vertical_target(text, 6, false), 2);
+    }
+
+    #[test]
+    fn clamps_to_shorter_line() {
+        let text = "a\ndefgh";
+        // caret at column 4 on line 1 -> line 0 only has 1 char, clamp to end.
+        assert_eq!(vertical_target(text, 6, false), 1);
+    }
+
+    #[test]
+    fn no_adjacent_line_is_noop() {
+        let text = "abc";
+        assert_eq!(vertical_target(text, 1, false), 1);
+        assert_eq!(vertical_target(text, 1, true), 1);
+    }
+}
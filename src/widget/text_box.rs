@@ -1,5 +1,7 @@
+use clipboard::{Clipboard, ClipboardContext, ClipboardHandle};
 use enums::ParentType;
 use event::{Key, KeyEventHandler};
+use layout_object::CursorLayoutObject;
 use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 use structs::{Focused, Label, WaterMark};
@@ -9,12 +11,30 @@ use widget::{
     Widget, WidgetContainer,
 };
 
+/// Caret position as a byte offset into the `Label` string, shared with the
+/// `Cursor` child. The `CursorLayoutObject` turns this byte offset into a pixel
+/// x-position by measuring the preceding glyphs against the active theme font.
+#[derive(Default, Clone, Copy)]
+pub struct CaretOffset(pub usize);
+
 /// The `TextBoxState` handles the text processing of the `TextBox` widget.
+///
+/// The caret is stored as a byte offset into the `Label` string and is
+/// guaranteed to always sit on a `char` boundary. When a selection is active
+/// `selection_anchor` holds the byte offset of the point where the selection
+/// started; the selected range is the span between `selection_anchor` and
+/// `caret`.
+///
+/// `State::update` reconciles the buffer against the shared `Label` each pass:
+/// edits made here flow out to `Label`, and programmatic changes written to
+/// `Label` through the component store flow back in.
 #[derive(Default)]
 pub struct TextBoxState {
     text: RefCell<String>,
     focused: Cell<bool>,
     updated: Cell<bool>,
+    caret: Cell<usize>,
+    selection_anchor: Cell<Option<usize>>,
 }
 
 impl Into<Rc<State>> for TextBoxState {
@@ -24,27 +44,196 @@ impl Into<Rc<State>> for TextBoxState {
 }
 
 impl TextBoxState {
+    /// Returns the selected byte range as an ordered `(start, end)` pair if a
+    /// selection is active and non-empty.
+    fn selection(&self) -> Option<(usize, usize)> {
+        self.selection_anchor.get().and_then(|anchor| {
+            let caret = self.caret.get();
+            if anchor == caret {
+                None
+            } else if anchor < caret {
+                Some((anchor, caret))
+            } else {
+                Some((caret, anchor))
+            }
+        })
+    }
+
+    /// Removes the current selection from the buffer and places the caret at
+    /// the start of the removed range. Returns `true` if anything was removed.
+    fn delete_selection(&self) -> bool {
+        if let Some((start, end)) = self.selection() {
+            self.text.borrow_mut().drain(start..end);
+            self.caret.set(start);
+            self.selection_anchor.set(None);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Byte offset of the char boundary preceding `caret`, or `caret` if it is
+    /// already at the start.
+    fn prev_boundary(&self) -> usize {
+        let text = self.text.borrow();
+        let caret = self.caret.get();
+        text[..caret]
+            .char_indices()
+            .next_back()
+            .map(|(i, _)| i)
+            .unwrap_or(0)
+    }
+
+    /// Byte offset of the char boundary following `caret`, or `caret` if it is
+    /// already at the end.
+    fn next_boundary(&self) -> usize {
+        let text = self.text.borrow();
+        let caret = self.caret.get();
+        match text[caret..].char_indices().nth(1) {
+            Some((i, _)) => caret + i,
+            None => text.len(),
+        }
+    }
+
+    /// Updates `selection_anchor` before a caret movement: Shift extends (or
+    /// starts) the selection from the current caret, any other movement clears
+    /// it.
+    fn prepare_move(&self, shift: bool) {
+        if shift {
+            if self.selection_anchor.get().is_none() {
+                self.selection_anchor.set(Some(self.caret.get()));
+            }
+        } else {
+            self.selection_anchor.set(None);
+        }
+    }
+
+    /// Returns the text currently selected, or the whole buffer if no
+    /// selection is active (used as the clipboard source for a bare copy).
+    fn selected_or_all(&self) -> String {
+        let text = self.text.borrow();
+        match self.selection() {
+            Some((start, end)) => text[start..end].to_string(),
+            None => text.clone(),
+        }
+    }
+
+    /// Handles `Ctrl+C` / `Ctrl+X` / `Ctrl+V`. Returns `true` if the key was
+    /// consumed so the caller can skip ordinary character insertion.
+    fn handle_clipboard(&self, key: Key, clipboard: &dyn Clipboard) -> bool {
+        if !self.focused.get() {
+            return false;
+        }
+
+        match <Option<u8>>::from(key).map(|byte| (byte as char).to_ascii_lowercase()) {
+            Some('c') => {
+                clipboard.set(self.selected_or_all());
+            }
+            Some('x') => {
+                clipboard.set(self.selected_or_all());
+                self.delete_selection();
+            }
+            Some('v') => {
+                if let Some(pasted) = clipboard.get() {
+                    self.delete_selection();
+                    let caret = self.caret.get();
+                    self.text.borrow_mut().insert_str(caret, &pasted);
+                    self.caret.set(caret + pasted.len());
+                    self.selection_anchor.set(None);
+                }
+            }
+            _ => return false,
+        }
+
+        self.clamp_caret();
+        self.updated.set(true);
+
+        true
+    }
+
     fn update_text(&self, key: Key) -> bool {
         if !self.focused.get() {
             return false;
         }
 
+        let shift = key.shift();
+
         match <Option<u8>>::from(key) {
             Some(byte) => {
-                (*self.text.borrow_mut()).push(byte as char);
+                self.delete_selection();
+                let c = byte as char;
+                let caret = self.caret.get();
+                self.text.borrow_mut().insert(caret, c);
+                self.caret.set(caret + c.len_utf8());
             }
             None => match key {
+                Key::Left => {
+                    self.prepare_move(shift);
+                    self.caret.set(self.prev_boundary());
+                }
+                Key::Right => {
+                    self.prepare_move(shift);
+                    self.caret.set(self.next_boundary());
+                }
+                Key::Home => {
+                    self.prepare_move(shift);
+                    self.caret.set(0);
+                }
+                Key::End => {
+                    self.prepare_move(shift);
+                    self.caret.set(self.text.borrow().len());
+                }
                 Key::Backspace => {
-                    (*self.text.borrow_mut()).pop();
+                    if !self.delete_selection() {
+                        let start = self.prev_boundary();
+                        let caret = self.caret.get();
+                        if start != caret {
+                            self.text.borrow_mut().drain(start..caret);
+                            self.caret.set(start);
+                        }
+                    }
+                }
+                Key::Delete => {
+                    if !self.delete_selection() {
+                        let end = self.next_boundary();
+                        let caret = self.caret.get();
+                        if end != caret {
+                            self.text.borrow_mut().drain(caret..end);
+                        }
+                    }
                 }
                 _ => {}
             },
         }
 
+        self.clamp_caret();
         self.updated.set(true);
 
         true
     }
+
+    /// Re-establishes the invariants after an edit: `caret` and
+    /// `selection_anchor` must stay within `0..=len` and never fall mid-codepoint.
+    fn clamp_caret(&self) {
+        let text = self.text.borrow();
+        self.caret.set(clamp_to_boundary(&text, self.caret.get()));
+
+        if let Some(anchor) = self.selection_anchor.get() {
+            self.selection_anchor
+                .set(Some(clamp_to_boundary(&text, anchor)));
+        }
+    }
+}
+
+/// Clamps `offset` into `0..=text.len()` and walks it back to the nearest
+/// preceding `char` boundary so a caret or selection endpoint never lands in
+/// the middle of a multi-byte codepoint.
+pub(crate) fn clamp_to_boundary(text: &str, offset: usize) -> usize {
+    let mut offset = offset.min(text.len());
+    while offset > 0 && !text.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
 }
 
 impl State for TextBoxState {
@@ -53,36 +242,44 @@ impl State for TextBoxState {
             self.focused.set(focused.0);
         }
 
+        // Reconcile the buffer against `Label`: our own edits (flagged by
+        // `updated`) are written out, otherwise an external change to the
+        // component store is adopted and the caret moved to the end.
         if let Ok(label) = widget.borrow_mut_property::<Label>() {
-            if label.0 == *self.text.borrow() {
-                return;
-            }
-
-            if self.updated.get() {
-                label.0 = self.text.borrow().clone();
-            } else {
-                *self.text.borrow_mut() = label.0.clone();
+            if label.0 != *self.text.borrow() {
+                if self.updated.get() {
+                    label.0 = self.text.borrow().clone();
+                } else {
+                    *self.text.borrow_mut() = label.0.clone();
+                    self.caret.set(self.text.borrow().len());
+                    self.selection_anchor.set(None);
+                }
             }
-
             self.updated.set(false);
         }
+
+        // Publish the caret byte offset so the `CursorLayoutObject` can measure
+        // its pixel x-position against the theme font and position the `Cursor`.
+        if let Ok(offset) = widget.borrow_mut_property::<CaretOffset>() {
+            offset.0 = self.caret.get();
+        }
     }
 }
 
 /// The `TextBox` represents a single line text input widget.
-/// 
+///
 /// # Shared Properties
-/// 
+///
 /// * `Label` - String used to display the text of the text box.
 /// * `Watermark` - String used to display a placeholder text if `Label` string is empty.
 /// * `Selector` - CSS selector used to request the theme of the widget.
-/// 
+///
 /// # Properties
-/// 
+///
 /// * `Focused` - Defines if the widget is focues and handles the current text input.
-/// 
+///
 /// # Others
-/// 
+///
 /// * `TextBoxState` - Handles the inner state of the widget.
 /// * `KeyEventHandler` - Process the text input of the control if it is focuesd.
 pub struct TextBox;
@@ -92,11 +289,13 @@ impl Widget for TextBox {
         let label = SharedProperty::new(Label::default());
         let water_mark = SharedProperty::new(WaterMark::default());
         let selector = SharedProperty::new(Selector::new().with("textbox"));
+        let caret_offset = SharedProperty::new(CaretOffset::default());
         let state = Rc::new(TextBoxState::default());
 
         Template::default()
             .as_parent_type(ParentType::Single)
             .with_property(Focused(false))
+            .with_property(ClipboardHandle::default())
             .with_child(
                 Container::create()
                     .with_child(
@@ -109,7 +308,13 @@ impl Widget for TextBox {
                                         .with_shared_property(water_mark.clone()),
                                 ),
                             )
-                            .with_child(Cursor::create()),
+                            .with_child(
+                                Cursor::create()
+                                    .with_layout_object(CursorLayoutObject)
+                                    .with_shared_property(caret_offset.clone())
+                                    .with_shared_property(label.clone())
+                                    .with_shared_property(selector.clone()),
+                            ),
                     )
                     .with_shared_property(selector.clone()),
             )
@@ -118,8 +323,35 @@ impl Widget for TextBox {
             .with_shared_property(label)
             .with_shared_property(selector)
             .with_shared_property(water_mark)
+            .with_shared_property(caret_offset)
             .with_event_handler(KeyEventHandler::default().on_key_down(Rc::new(
-                move |key: Key, _widget: &mut WidgetContainer| -> bool { state.update_text(key) },
+                move |key: Key, widget: &mut WidgetContainer| -> bool {
+                    if key.control() {
+                        let clipboard = widget.clipboard();
+                        state.handle_clipboard(key, &*clipboard)
+                    } else {
+                        state.update_text(key)
+                    }
+                },
             )))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::clamp_to_boundary;
+
+    #[test]
+    fn clamps_past_end_to_len() {
+        assert_eq!(clamp_to_boundary("abc", 9), 3);
+    }
+
+    #[test]
+    fn walks_back_off_multibyte_boundary() {
+        // "é" is two bytes; offset 1 is mid-codepoint and must snap back to 0.
+        let text = "é";
+        assert_eq!(text.len(), 2);
+        assert_eq!(clamp_to_boundary(text, 1), 0);
+        assert_eq!(clamp_to_boundary(text, 2), 2);
+    }
+}